@@ -0,0 +1,206 @@
+use prometheus::{GaugeVec, Opts, Registry};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// A metrics-gathering strategy for a single Satisfactory dedicated-server API function.
+///
+/// The scrape loop POSTs `{"function": collector.function_name()}` once per tick and routes the
+/// response's `data` field to `update`. Each collector owns and registers its own gauges, so a
+/// malformed response from one function only logs a warning and never drops the metrics the other
+/// collectors already gathered.
+pub trait Collector: Send + Sync {
+    /// The dedicated-server API function this collector queries, e.g. `"QueryServerState"`
+    fn function_name(&self) -> &'static str;
+
+    /// Registers this collector's gauges with `registry`
+    fn register(&self, registry: &Registry) -> prometheus::Result<()>;
+
+    /// Parses the `data` field of the function's response and updates the gauges for `target`
+    fn update(&self, target: &str, data: Value) -> Result<(), String>;
+}
+
+/// Structure for the server game state within `QueryServerState`'s response data
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ServerGameState {
+    num_connected_players: u64,
+    tech_tier: u64,
+    total_game_duration: u64,
+    average_tick_rate: f64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QueryServerStateData {
+    server_game_state: ServerGameState,
+}
+
+/// Collector for `QueryServerState`: the core player count, tech tier, game duration, and tick rate gauges
+pub struct ServerStateCollector {
+    num_connected_players: GaugeVec,
+    tech_tier: GaugeVec,
+    total_game_duration: GaugeVec,
+    average_tick_rate: GaugeVec,
+}
+
+impl ServerStateCollector {
+    pub fn new() -> Self {
+        let vec = |name, help| GaugeVec::new(Opts::new(name, help), &["target"]).unwrap();
+        ServerStateCollector {
+            // Kept un-prefixed: these are the original pre-refactor metric names, and renaming
+            // them would silently break every dashboard/alert built against them.
+            num_connected_players: vec("num_connected_players", "Number of connected players"),
+            tech_tier: vec("tech_tier", "Current tech tier"),
+            total_game_duration: vec("total_game_duration", "Total game duration"),
+            average_tick_rate: vec("average_tick_rate", "Average tick rate"),
+        }
+    }
+}
+
+impl Collector for ServerStateCollector {
+    fn function_name(&self) -> &'static str {
+        "QueryServerState"
+    }
+
+    fn register(&self, registry: &Registry) -> prometheus::Result<()> {
+        registry.register(Box::new(self.num_connected_players.clone()))?;
+        registry.register(Box::new(self.tech_tier.clone()))?;
+        registry.register(Box::new(self.total_game_duration.clone()))?;
+        registry.register(Box::new(self.average_tick_rate.clone()))?;
+        Ok(())
+    }
+
+    fn update(&self, target: &str, data: Value) -> Result<(), String> {
+        let data: QueryServerStateData = serde_json::from_value(data).map_err(|e| e.to_string())?;
+        let game_state = data.server_game_state;
+        self.num_connected_players.with_label_values(&[target]).set(game_state.num_connected_players as f64);
+        self.tech_tier.with_label_values(&[target]).set(game_state.tech_tier as f64);
+        self.total_game_duration.with_label_values(&[target]).set(game_state.total_game_duration as f64);
+        self.average_tick_rate.with_label_values(&[target]).set(game_state.average_tick_rate);
+        Ok(())
+    }
+}
+
+/// Structure for the subset of `GetServerOptions`'s `serverOptions` map this collector exposes
+#[derive(Deserialize)]
+struct ServerOptions {
+    #[serde(rename = "FG.AutosaveInterval")]
+    autosave_interval: f64,
+    #[serde(rename = "FG.TickRateCap")]
+    tick_rate_cap: f64,
+}
+
+#[derive(Deserialize)]
+struct GetServerOptionsData {
+    #[serde(rename = "serverOptions")]
+    server_options: ServerOptions,
+}
+
+/// Collector for `GetServerOptions`: the configured autosave interval and tick-rate cap
+pub struct ServerOptionsCollector {
+    autosave_interval_seconds: GaugeVec,
+    tick_rate_cap: GaugeVec,
+}
+
+impl ServerOptionsCollector {
+    pub fn new() -> Self {
+        let vec = |name, help| GaugeVec::new(Opts::new(name, help), &["target"]).unwrap();
+        ServerOptionsCollector {
+            autosave_interval_seconds: vec("satisfactory_autosave_interval_seconds", "Configured interval between autosaves, in seconds"),
+            tick_rate_cap: vec("satisfactory_tick_rate_cap", "Configured maximum simulation tick rate"),
+        }
+    }
+}
+
+impl Collector for ServerOptionsCollector {
+    fn function_name(&self) -> &'static str {
+        "GetServerOptions"
+    }
+
+    fn register(&self, registry: &Registry) -> prometheus::Result<()> {
+        registry.register(Box::new(self.autosave_interval_seconds.clone()))?;
+        registry.register(Box::new(self.tick_rate_cap.clone()))?;
+        Ok(())
+    }
+
+    fn update(&self, target: &str, data: Value) -> Result<(), String> {
+        let data: GetServerOptionsData = serde_json::from_value(data).map_err(|e| e.to_string())?;
+        self.autosave_interval_seconds.with_label_values(&[target]).set(data.server_options.autosave_interval);
+        self.tick_rate_cap.with_label_values(&[target]).set(data.server_options.tick_rate_cap);
+        Ok(())
+    }
+}
+
+/// Structure for a single entry of `EnumerateSessions`'s `sessions` array
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionSummary {
+    session_name: String,
+    saves: Vec<Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EnumerateSessionsData {
+    sessions: Vec<SessionSummary>,
+    current_session_index: i64,
+}
+
+/// Collector for `EnumerateSessions`: how many saves exist for the active session, and which
+/// session is currently active (exposed as a Prometheus "info" gauge, labeled by session name)
+pub struct SessionCollector {
+    save_count: GaugeVec,
+    session_info: GaugeVec,
+    /// The `session_name` last reported for each target, so a rename can clear just that
+    /// target's stale series instead of resetting the whole (shared, multi-target) `GaugeVec`
+    last_session_name: Mutex<HashMap<String, String>>,
+}
+
+impl SessionCollector {
+    pub fn new() -> Self {
+        SessionCollector {
+            save_count: GaugeVec::new(Opts::new("satisfactory_save_count", "Number of saves for the active session"), &["target"]).unwrap(),
+            session_info: GaugeVec::new(Opts::new("satisfactory_session_info", "Always 1; identifies the active session by its `session_name` label"), &["target", "session_name"]).unwrap(),
+            last_session_name: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Collector for SessionCollector {
+    fn function_name(&self) -> &'static str {
+        "EnumerateSessions"
+    }
+
+    fn register(&self, registry: &Registry) -> prometheus::Result<()> {
+        registry.register(Box::new(self.save_count.clone()))?;
+        registry.register(Box::new(self.session_info.clone()))?;
+        Ok(())
+    }
+
+    fn update(&self, target: &str, data: Value) -> Result<(), String> {
+        let data: EnumerateSessionsData = serde_json::from_value(data).map_err(|e| e.to_string())?;
+        let current_session = usize::try_from(data.current_session_index)
+            .ok()
+            .and_then(|i| data.sessions.get(i))
+            .ok_or_else(|| format!("no session at currentSessionIndex {}", data.current_session_index))?;
+
+        self.save_count.with_label_values(&[target]).set(current_session.saves.len() as f64);
+
+        // The vec is shared across every target's scrape loop, so clear just this target's own
+        // stale session_name series (if the session was renamed) rather than resetting the whole vec
+        let mut last_session_name = self.last_session_name.lock().unwrap();
+        if let Some(old_name) = last_session_name.get(target) {
+            if old_name != &current_session.session_name {
+                let _ = self.session_info.remove_label_values(&[target, old_name]);
+            }
+        }
+        last_session_name.insert(target.to_string(), current_session.session_name.clone());
+        drop(last_session_name);
+
+        self.session_info.with_label_values(&[target, &current_session.session_name]).set(1.0);
+
+        Ok(())
+    }
+}
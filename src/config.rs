@@ -0,0 +1,73 @@
+use crate::Args;
+use figment::providers::{Env, Format, Serialized, Toml};
+use figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// A single Satisfactory dedicated server to scrape, as defined in a `[[servers]]` entry of the
+/// TOML config file
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ServerConfig {
+    /// Hostname and port of the server to query
+    pub endpoint: String,
+
+    /// File containing the bearer token to use for authentication
+    pub token_file: Option<String>,
+
+    /// Allow insecure connections (e.g., to a server with a self-signed certificate)
+    #[serde(default)]
+    pub allow_insecure: bool,
+
+    /// Interval in seconds between each query to this server. Defaults to the top-level `update_interval`.
+    pub update_interval: Option<u64>,
+}
+
+fn default_update_interval() -> u64 {
+    5
+}
+
+fn default_listen() -> String {
+    "127.0.0.1:3030".to_string()
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+/// Fully merged application configuration, built from (lowest to highest priority) built-in
+/// defaults, a TOML file, environment variables prefixed with `SATISFACTORY_EXPORTER_`, and the
+/// parsed CLI flags
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AppConfig {
+    #[serde(default = "default_update_interval")]
+    pub update_interval: u64,
+    pub endpoint: Option<String>,
+    pub token_file: Option<String>,
+    #[serde(default)]
+    pub allow_insecure: bool,
+    #[serde(default = "default_listen")]
+    pub listen: String,
+    pub otlp_endpoint: Option<String>,
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+
+    /// Servers to scrape, feeding the multi-target collector. When empty, the top-level `endpoint`
+    /// is scraped as the sole target, preserving the original single-server behavior; when
+    /// non-empty, the top-level `endpoint` is not scraped.
+    #[serde(default)]
+    pub servers: Vec<ServerConfig>,
+}
+
+/// Loads the application configuration, layering a TOML file (`--config`), `SATISFACTORY_EXPORTER_*`
+/// environment variables, and the parsed CLI flags on top of each other in that priority order.
+pub fn load(args: &Args) -> Result<AppConfig, figment::Error> {
+    let mut figment = Figment::new();
+
+    if let Some(path) = &args.config {
+        figment = figment.merge(Toml::file(path));
+    }
+
+    figment
+        .merge(Env::prefixed("SATISFACTORY_EXPORTER_"))
+        .merge(Serialized::defaults(args))
+        .extract()
+}
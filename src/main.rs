@@ -1,42 +1,131 @@
 use axum::{
-    extract::State,
+    extract::{Query, State},
     response::IntoResponse,
     routing::get,
     Router,
 };
 use clap::Parser;
-use prometheus::{Encoder, Gauge, Registry, TextEncoder};
+use opentelemetry::sdk::trace as sdktrace;
+use opentelemetry::sdk::Resource;
+use opentelemetry::KeyValue;
+use prometheus::{Encoder, GaugeVec, Opts, Registry, TextEncoder};
+use rand::Rng;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::fs;
 use std::str::FromStr;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tokio::signal;
+use tokio::sync::watch;
 use tokio::time::interval;
+use tracing::{error, info, warn, Instrument};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
-/// Command line arguments structure
-#[derive(Parser, Debug)]
+use collectors::{Collector, ServerOptionsCollector, ServerStateCollector, SessionCollector};
+
+mod collectors;
+mod config;
+
+/// Command line arguments structure.
+///
+/// Every field (besides `config` and `allow_insecure`) is optional here so that [`config::load`]
+/// can tell "not passed on the CLI" apart from "explicitly set" and let config file / environment
+/// variable values show through instead of being clobbered by a clap default.
+#[derive(Parser, Debug, Serialize)]
 #[command(author, version, about, long_about = None)]
 struct Args {
     /// Interval in seconds between each query to the server
-    #[arg(short, long, default_value = "5", help="Interval in seconds between each query to the server")]
-    update_interval: u64,
+    #[arg(short, long, help="Interval in seconds between each query to the server")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    update_interval: Option<u64>,
 
-    /// Hostname and port of the server to query
+    /// Hostname and port of the server to query. Not required when the config file defines `[[servers]]`.
     #[arg(short, long, help="Hostname and port of the server to query")]
-    endpoint: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    endpoint: Option<String>,
 
     /// File containing the bearer token to use for authentication
     #[arg(short, long, help="File containing the bearer token to use for authentication")]
+    #[serde(skip_serializing_if = "Option::is_none")]
     token_file: Option<String>,
 
-    /// Allow insecure connections (e.g., to a server with a self-signed certificate)
+    /// Allow insecure connections (e.g., to a server with a self-signed certificate). Only ever
+    /// overrides the config file/environment when actually passed, since there's no way to tell
+    /// an absent flag from an explicit `false`.
     #[arg(short, long, help="Allow insecure connections (e.g., to a server with a self-signed certificate)")]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
     allow_insecure: bool,
 
     /// Address:Port to which the server will listen
-    #[arg(short, long, help="Address:Port to which the server will listen", default_value = "127.0.0.1:3030")]
-    listen: String,
+    #[arg(short, long, help="Address:Port to which the server will listen")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    listen: Option<String>,
+
+    /// OTLP collector endpoint to export traces and logs to (e.g. http://localhost:4317). Logs are printed to stdout whether or not this is set.
+    #[arg(long, help="OTLP collector endpoint to export traces and logs to (e.g. http://localhost:4317)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    otlp_endpoint: Option<String>,
+
+    /// Maximum number of retries for a failed scrape before giving up for that tick
+    #[arg(long, help="Maximum number of retries for a failed scrape before giving up for that tick")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_retries: Option<u32>,
+
+    /// Path to a TOML config file, merged with environment variables and these CLI flags (CLI wins)
+    #[arg(short, long, help="Path to a TOML config file, merged with environment variables and these CLI flags (CLI wins)")]
+    #[serde(skip)]
+    config: Option<String>,
+}
+
+/// Base delay for the first retry; doubled on each subsequent attempt and capped at `update_interval`
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(200);
+
+/// Maximum random jitter added to each retry delay, to avoid thundering-herd retries
+const RETRY_JITTER_MAX: Duration = Duration::from_millis(100);
+
+/// Fraction of `update_interval` a scrape may take before it's logged as slow
+const SLOW_SCRAPE_WARN_RATIO: f64 = 0.8;
+
+/// Initializes the global tracing subscriber.
+///
+/// When `otlp_endpoint` is provided, spans and events are exported to an OTLP collector via
+/// `tracing-opentelemetry`. Otherwise, falls back to a plain `fmt` subscriber writing to stdout.
+fn init_tracing(otlp_endpoint: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+
+    match otlp_endpoint {
+        Some(endpoint) => {
+            let tracer = opentelemetry_otlp::new_pipeline()
+                .tracing()
+                .with_exporter(
+                    opentelemetry_otlp::new_exporter()
+                        .tonic()
+                        .with_endpoint(endpoint),
+                )
+                .with_trace_config(sdktrace::config().with_resource(Resource::new(vec![
+                    KeyValue::new("service.name", "satisfactory-exporter"),
+                ])))
+                .install_batch(opentelemetry::runtime::Tokio)?;
+
+            let otel_layer = tracing_opentelemetry::layer().with_tracer(tracer);
+
+            tracing_subscriber::registry()
+                .with(env_filter)
+                .with(tracing_subscriber::fmt::layer())
+                .with(otel_layer)
+                .try_init()?;
+        }
+        None => {
+            tracing_subscriber::registry()
+                .with(env_filter)
+                .with(tracing_subscriber::fmt::layer())
+                .try_init()?;
+        }
+    }
+
+    Ok(())
 }
 
 /// Structure for the query body sent to the server
@@ -45,144 +134,396 @@ struct QueryBody {
     function: String,
 }
 
-/// Structure for the server response
-#[derive(Deserialize)]
-struct ServerResponse {
-    data: ServerData,
-}
-
-/// Structure for the server data within the server response
+/// Structure for the outer envelope of every dedicated-server API response: `{"data": ...}`
 #[derive(Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct ServerData {
-    server_game_state: ServerGameState,
+struct FunctionResponse {
+    data: Value,
 }
 
-/// Structure for the server game state within the server data
-#[derive(Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct ServerGameState {
-    num_connected_players: u64,
-    tech_tier: u64,
-    total_game_duration: u64,
-    average_tick_rate: f64,
+/// The [`Collector`]s this exporter ships with. The first collector is the "primary" one: whether
+/// its response arrives and parses drives the `satisfactory_up` gauge, and its request failures
+/// drive the retry policy; the rest are queried
+/// best-effort, once per tick, so a broken/unsupported function never takes down the others.
+fn default_collectors() -> Vec<Arc<dyn Collector>> {
+    vec![
+        Arc::new(ServerStateCollector::new()),
+        Arc::new(ServerOptionsCollector::new()),
+        Arc::new(SessionCollector::new()),
+    ]
 }
 
-/// Structure for the metrics to be collected
-#[derive(Clone)]
-struct Metrics {
-    num_connected_players: Gauge,
-    tech_tier: Gauge,
-    total_game_duration: Gauge,
-    average_tick_rate: Gauge,
+/// Tick-level gauges that describe the scrape itself rather than any one API function's data
+struct TickMetrics {
+    /// 1 if the primary collector's function succeeded this tick, 0 otherwise. Mirrors Prometheus's `up` convention.
+    up: GaugeVec,
+    /// Wall-clock time the whole tick took, including retries and the secondary collectors
+    scrape_duration_seconds: GaugeVec,
 }
 
-impl Metrics {
-    /// Creates a new instance of `Metrics`
+impl TickMetrics {
     fn new() -> Self {
-        Metrics {
-            num_connected_players: Gauge::new("num_connected_players", "Number of connected players").unwrap(),
-            tech_tier: Gauge::new("tech_tier", "Current tech tier").unwrap(),
-            total_game_duration: Gauge::new("total_game_duration", "Total game duration").unwrap(),
-            average_tick_rate: Gauge::new("average_tick_rate", "Average tick rate").unwrap(),
+        TickMetrics {
+            up: GaugeVec::new(Opts::new("satisfactory_up", "Whether the last scrape of the server succeeded (1) or failed (0)"), &["target"]).unwrap(),
+            scrape_duration_seconds: GaugeVec::new(Opts::new("satisfactory_scrape_duration_seconds", "Duration of the last scrape, including retries, in seconds"), &["target"]).unwrap(),
         }
     }
 
-    /// Updates the metrics with the provided game state
-    fn update(&self, game_state: &ServerGameState) {
-        self.num_connected_players.set(game_state.num_connected_players as f64);
-        self.tech_tier.set(game_state.tech_tier as f64);
-        self.total_game_duration.set(game_state.total_game_duration as f64);
-        self.average_tick_rate.set(game_state.average_tick_rate);
+    fn register(&self, registry: &Registry) -> prometheus::Result<()> {
+        registry.register(Box::new(self.up.clone()))?;
+        registry.register(Box::new(self.scrape_duration_seconds.clone()))?;
+        Ok(())
     }
 }
 
+/// A single periodically-scraped server
+struct ScrapeTarget {
+    /// Human-readable label for this target, used in logs, tracing spans, and the `target` metric label
+    label: String,
+    /// Fully qualified query endpoint, e.g. `https://host:port/api/v1`
+    endpoint: String,
+    bearer_token: Option<String>,
+    update_interval: Duration,
+}
+
+/// Calls `function` against `endpoint` and returns its `data` field
+async fn call_function(client: &Client, endpoint: &str, bearer_token: Option<&str>, function: &str) -> Result<Value, String> {
+    let query_body = QueryBody {
+        function: function.to_string(),
+    };
+
+    let mut request = client.post(endpoint).json(&query_body);
+    if let Some(token) = bearer_token {
+        request = request.bearer_auth(token.trim());
+    }
+
+    let response = request.send().await.map_err(|e| e.to_string())?;
+    tracing::Span::current().record("http.status_code", response.status().as_u16());
+    response.json::<FunctionResponse>().await.map(|r| r.data).map_err(|e| e.to_string())
+}
+
+/// Queries every secondary collector once and routes each response to its `update`. Failures are
+/// logged and otherwise ignored, since a single malformed/unsupported function shouldn't affect
+/// the other collectors or the tick's overall `up`/duration metrics.
+async fn run_secondary_collectors(client: &Client, target: &ScrapeTarget, collectors: &[Arc<dyn Collector>]) {
+    for collector in collectors {
+        let function = collector.function_name();
+        match call_function(client, &target.endpoint, target.bearer_token.as_deref(), function).await {
+            Ok(data) => {
+                if let Err(e) = collector.update(&target.label, data) {
+                    warn!(error = %e, target = %target.label, function, "Failed to parse collector response");
+                }
+            }
+            Err(e) => warn!(error = %e, target = %target.label, function, "Failed to query collector"),
+        }
+    }
+}
+
+/// Spawns a task that polls `target` on `target.update_interval`. Each tick, the primary
+/// collector (`collectors[0]`) is queried with retries (exponential backoff plus jitter, up to
+/// `max_retries`) to determine `satisfactory_up`, then every other collector is queried once,
+/// best-effort. Once `shutdown` is signalled, the task finishes whatever tick it's currently on
+/// (it never aborts a scrape mid-flight) and then exits before starting another one.
+fn spawn_scrape_loop(
+    client: Client,
+    target: ScrapeTarget,
+    max_retries: u32,
+    mut shutdown: watch::Receiver<bool>,
+    collectors: Arc<Vec<Arc<dyn Collector>>>,
+    tick_metrics: Arc<TickMetrics>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = interval(target.update_interval);
+        let (primary, secondary) = collectors.split_first().expect("at least one collector must be configured");
+
+        loop {
+            // Biased, and shutdown listed first, so a pending shutdown always wins a tick that
+            // raced it (e.g. a scrape that overran the interval leaves both branches ready at
+            // once) instead of `select!`'s default pseudo-random choice letting one more tick slip through
+            tokio::select! {
+                biased;
+                _ = shutdown.changed() => {
+                    info!(target = %target.label, "Scrape loop shutting down");
+                    break;
+                }
+                _ = interval.tick() => {
+                    if *shutdown.borrow() {
+                        info!(target = %target.label, "Scrape loop shutting down");
+                        break;
+                    }
+                }
+            }
+
+            let span = tracing::info_span!("scrape", target = %target.label, function = primary.function_name(), attempt = tracing::field::Empty, http.status_code = tracing::field::Empty, elapsed_ms = tracing::field::Empty);
+            let started_at = Instant::now();
+
+            let reachable = async {
+                let mut attempt = 0u32;
+                let mut backoff = RETRY_BASE_DELAY;
+
+                loop {
+                    attempt += 1;
+                    tracing::Span::current().record("attempt", attempt);
+
+                    match call_function(&client, &target.endpoint, target.bearer_token.as_deref(), primary.function_name()).await {
+                        Ok(data) => {
+                            if let Err(e) = primary.update(&target.label, data) {
+                                warn!(error = %e, target = %target.label, function = primary.function_name(), "Failed to parse collector response");
+                                break false;
+                            }
+                            break true;
+                        }
+                        Err(e) if attempt > max_retries => {
+                            error!(error = %e, attempt, target = %target.label, "Scrape failed after exhausting retries");
+                            break false;
+                        }
+                        Err(e) => {
+                            warn!(error = %e, attempt, target = %target.label, retry_in_ms = backoff.as_millis() as u64, "Scrape failed, retrying");
+                            let jitter = Duration::from_millis(rand::thread_rng().gen_range(0..RETRY_JITTER_MAX.as_millis() as u64));
+                            tokio::time::sleep(backoff.min(target.update_interval) + jitter).await;
+                            // Cap the stored backoff itself, not just the sleep, so it can't grow
+                            // past `update_interval` and eventually overflow `Duration::mul` on a
+                            // persistently-failing target with a large `--max-retries`
+                            backoff = backoff.saturating_mul(2).min(target.update_interval);
+                        }
+                    }
+                }
+            }
+            .instrument(span.clone())
+            .await;
+
+            run_secondary_collectors(&client, &target, secondary).await;
+
+            let elapsed = started_at.elapsed();
+            span.record("elapsed_ms", elapsed.as_millis() as u64);
+
+            if elapsed.as_secs_f64() > target.update_interval.as_secs_f64() * SLOW_SCRAPE_WARN_RATIO {
+                warn!(target = %target.label, elapsed_ms = elapsed.as_millis() as u64, "Scrape took longer than the slowness threshold");
+            }
+
+            tick_metrics.scrape_duration_seconds.with_label_values(&[target.label.as_str()]).set(elapsed.as_secs_f64());
+            tick_metrics.up.with_label_values(&[target.label.as_str()]).set(if reachable { 1.0 } else { 0.0 });
+        }
+    })
+}
+
+/// State shared across all Axum handlers
+struct AppState {
+    registry: Registry,
+    client: Client,
+    bearer_token: Option<String>,
+}
+
 /// Shared state type alias
-type SharedState = Arc<(Metrics, Registry)>;
+type SharedState = Arc<AppState>;
 
-/// Handler for the `/metrics` endpoint
+/// Handler for the `/metrics` endpoint: reports the metrics gathered by the background scrape loop
 async fn metrics_handler(State(state): State<SharedState>) -> impl IntoResponse {
     let encoder = TextEncoder::new();
     let mut buffer = Vec::new();
-    encoder.encode(&state.1.gather(), &mut buffer).unwrap();
+    encoder.encode(&state.registry.gather(), &mut buffer).unwrap();
     String::from_utf8(buffer).unwrap()
 }
 
-/// Main function
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Parse command line arguments
-    let args = Args::parse();
+/// Query parameters accepted by the `/probe` endpoint
+#[derive(Deserialize)]
+struct ProbeParams {
+    target: String,
+}
 
-    // Create a new registry and metrics instance
+/// Handler for the `/probe` endpoint: performs a one-shot scrape of `target` (running every
+/// collector once, with no retries) and returns its metrics on a fresh `Registry`, so concurrent
+/// probes of different servers never clobber each other's gauges.
+async fn probe_handler(State(state): State<SharedState>, Query(params): Query<ProbeParams>) -> impl IntoResponse {
     let registry = Registry::new();
-    let metrics = Arc::new(Metrics::new());
+    let collectors = default_collectors();
+    for collector in &collectors {
+        collector.register(&registry).unwrap();
+    }
+    let tick_metrics = TickMetrics::new();
+    tick_metrics.register(&registry).unwrap();
+
+    let endpoint = format!("https://{}/api/v1", params.target);
+    let (primary, secondary) = collectors.split_first().expect("at least one collector must be configured");
+
+    let span = tracing::info_span!("probe", target = %params.target, function = primary.function_name(), http.status_code = tracing::field::Empty);
+    let started_at = Instant::now();
+    let result = call_function(&state.client, &endpoint, state.bearer_token.as_deref(), primary.function_name())
+        .instrument(span)
+        .await;
+
+    let reachable = match result {
+        Ok(data) => {
+            if let Err(e) = primary.update(&params.target, data) {
+                warn!(target = %params.target, error = %e, "Probe failed to parse response");
+                false
+            } else {
+                true
+            }
+        }
+        Err(e) => {
+            warn!(target = %params.target, error = %e, "Probe failed");
+            false
+        }
+    };
+
+    let target = ScrapeTarget {
+        label: params.target.clone(),
+        endpoint,
+        bearer_token: state.bearer_token.clone(),
+        update_interval: Duration::ZERO,
+    };
+    run_secondary_collectors(&state.client, &target, secondary).await;
 
-    // Register metrics with the registry
-    registry.register(Box::new(metrics.num_connected_players.clone())).unwrap();
-    registry.register(Box::new(metrics.tech_tier.clone())).unwrap();
-    registry.register(Box::new(metrics.total_game_duration.clone())).unwrap();
-    registry.register(Box::new(metrics.average_tick_rate.clone())).unwrap();
+    let elapsed = started_at.elapsed();
+    tick_metrics.scrape_duration_seconds.with_label_values(&[params.target.as_str()]).set(elapsed.as_secs_f64());
+    tick_metrics.up.with_label_values(&[params.target.as_str()]).set(if reachable { 1.0 } else { 0.0 });
+
+    let encoder = TextEncoder::new();
+    let mut buffer = Vec::new();
+    encoder.encode(&registry.gather(), &mut buffer).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
 
-    // Create shared state
-    let shared_state: SharedState = Arc::new(((*metrics).clone(), registry));
+/// Resolves once SIGINT or (on Unix) SIGTERM is received
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        signal::ctrl_c().await.expect("Failed to install Ctrl+C handler");
+    };
 
-    // Clone metrics for use in the update loop
-    let metrics_clone = Arc::clone(&metrics);
-    let update_interval = Duration::from_secs(args.update_interval);
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
 
-    // Build the HTTP client
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+/// Builds a `reqwest::Client`, optionally accepting invalid TLS certs
+fn build_client(allow_insecure: bool) -> reqwest::Result<Client> {
     let mut client_builder = Client::builder();
-    if args.allow_insecure {
+    if allow_insecure {
         client_builder = client_builder.danger_accept_invalid_certs(true);
     }
-    let client = client_builder.build()?;
+    client_builder.build()
+}
 
-    // Read the bearer token if provided
-    let bearer_token = args.token_file.map(|file| fs::read_to_string(file).expect("Failed to read token file"));
+/// Main function
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Parse command line arguments, then layer a TOML config file and environment variables underneath them
+    let args = Args::parse();
+    let cfg = config::load(&args)?;
 
-    let query_endpoint = format!("https://{}/api/v1", args.endpoint);
+    // Set up structured logging/tracing, optionally exporting to an OTLP collector
+    init_tracing(cfg.otlp_endpoint.as_deref())?;
 
-    // Spawn a task to periodically query the server and update metrics
-    tokio::spawn(async move {
-        let mut interval = interval(update_interval);
-        let query_body = QueryBody {
-            function: "QueryServerState".to_string(),
-        };
+    // Create a new registry and the shared set of collectors, used for every target below
+    let registry = Registry::new();
+    let collectors = Arc::new(default_collectors());
+    for collector in collectors.iter() {
+        collector.register(&registry).unwrap();
+    }
+    let tick_metrics = Arc::new(TickMetrics::new());
+    tick_metrics.register(&registry).unwrap();
 
-        loop {
-            interval.tick().await;
-            let mut request = client.post(&query_endpoint).json(&query_body);
+    let max_retries = cfg.max_retries;
 
-            if let Some(token) = &bearer_token {
-                request = request.bearer_auth(token.trim());
-            }
+    // Build a default client/token, used for the single-target scrape loop (if any) and for ad-hoc `/probe` requests
+    let default_client = build_client(cfg.allow_insecure)?;
+    let default_bearer_token = cfg.token_file.as_ref().map(|file| fs::read_to_string(file).expect("Failed to read token file"));
 
-            match request.send().await {
-                Ok(response) => {
-                    match response.json::<ServerResponse>().await {
-                        Ok(server_response) => {
-                            metrics_clone.update(&server_response.data.server_game_state);
-                        }
-                        Err(e) => eprintln!("Failed to parse service metrics: {}", e),
-                    }
-                }
-                Err(e) => eprintln!("Failed to fetch metrics: {}", e),
-            }
+    // Tells every spawned scrape loop to finish its current tick and exit once we receive SIGINT/SIGTERM
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+    // Joined after the server stops, so "finish the current tick then exit" is an actual
+    // guarantee rather than just a signal we fire and forget
+    let mut scrape_tasks = Vec::new();
+
+    if cfg.servers.is_empty() {
+        // No `[[servers]]` configured: scrape the single top-level `--endpoint` target, as before
+        let endpoint = cfg.endpoint.clone().ok_or("either --endpoint or a [[servers]] config entry is required")?;
+        let query_endpoint = format!("https://{}/api/v1", endpoint);
+        let update_interval = Duration::from_secs(cfg.update_interval);
+
+        scrape_tasks.push(spawn_scrape_loop(
+            default_client.clone(),
+            ScrapeTarget {
+                label: endpoint,
+                endpoint: query_endpoint,
+                bearer_token: default_bearer_token.clone(),
+                update_interval,
+            },
+            max_retries,
+            shutdown_rx.clone(),
+            Arc::clone(&collectors),
+            Arc::clone(&tick_metrics),
+        ));
+    } else {
+        // `[[servers]]` configured: scrape each one, sharing the same target-labeled registry
+        for server in &cfg.servers {
+            let client = build_client(server.allow_insecure)?;
+            let bearer_token = server.token_file.as_ref().map(|file| fs::read_to_string(file).expect("Failed to read token file"));
+            let update_interval = Duration::from_secs(server.update_interval.unwrap_or(cfg.update_interval));
+            let query_endpoint = format!("https://{}/api/v1", server.endpoint);
+
+            scrape_tasks.push(spawn_scrape_loop(
+                client,
+                ScrapeTarget {
+                    label: server.endpoint.clone(),
+                    endpoint: query_endpoint,
+                    bearer_token,
+                    update_interval,
+                },
+                max_retries,
+                shutdown_rx.clone(),
+                Arc::clone(&collectors),
+                Arc::clone(&tick_metrics),
+            ));
         }
+    }
+
+    // Create shared state, handed to both the `/metrics` handler and the on-demand `/probe` handler
+    let shared_state: SharedState = Arc::new(AppState {
+        registry,
+        client: default_client,
+        bearer_token: default_bearer_token,
     });
 
     // Build the application router
     let app = Router::new()
         .route("/metrics", get(metrics_handler))
+        .route("/probe", get(probe_handler))
         .with_state(shared_state);
 
-    // Start the server
-    let addr = std::net::SocketAddr::from_str(&args.listen)?;
-    println!("Listening on {}", addr);
+    // Start the server, shutting down gracefully (no new requests, scrape loops wind down) on SIGINT/SIGTERM
+    let addr = std::net::SocketAddr::from_str(&cfg.listen)?;
+    info!(%addr, "Listening");
     axum::Server::bind(&addr)
         .serve(app.into_make_service())
+        .with_graceful_shutdown(async move {
+            shutdown_signal().await;
+            info!("Shutdown signal received, waiting for in-flight scrapes to finish");
+            let _ = shutdown_tx.send(true);
+        })
         .await?;
 
+    // The server has stopped accepting new requests; wait for every scrape loop to finish its
+    // current tick (or exit immediately, if it was already idle at the interval tick) before exiting
+    for task in scrape_tasks {
+        let _ = task.await;
+    }
+
+    info!("Exited cleanly");
+    opentelemetry::global::shutdown_tracer_provider();
+
     Ok(())
 }